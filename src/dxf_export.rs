@@ -0,0 +1,83 @@
+//! Translate the shared [`Primitive`] stream collected while walking the Gerber command
+//! stream in [`crate::Gerber2SVG::build`] into a `dxf` crate [`Drawing`], mirroring the SVG
+//! backend for CAD/CAM import.
+use dxf::entities::{Circle, Entity, EntityType, LwPolyline, LwPolylineVertex};
+use dxf::{Drawing, Point};
+
+use crate::primitive::Primitive;
+use crate::Gerber2SVG;
+
+/// Bulge factor for an LWPOLYLINE vertex starting an arc swept through `angle` radians
+/// (positive counter-clockwise, negative clockwise), per the DXF spec.
+fn bulge(angle: f64) -> f64 {
+    (angle / 4.0).tan()
+}
+
+fn vertex(x: f64, y: f64, bulge_value: f64) -> LwPolylineVertex {
+    LwPolylineVertex {
+        x,
+        y,
+        bulge: bulge_value,
+        ..Default::default()
+    }
+}
+
+fn add_polyline(drawing: &mut Drawing, vertices: Vec<LwPolylineVertex>, closed: bool) {
+    let mut polyline = LwPolyline {
+        vertices,
+        ..Default::default()
+    };
+    polyline.set_is_closed(closed);
+    drawing.add_entity(Entity::new(EntityType::LwPolyline(polyline)));
+}
+
+fn add_contour(drawing: &mut Drawing, contour: &[(f64, f64)]) {
+    let vertices = contour.iter().map(|(x, y)| vertex(*x, *y, 0.0)).collect();
+    add_polyline(drawing, vertices, true);
+}
+
+/// Build a DXF drawing from every primitive recorded while walking the Gerber command stream.
+pub(crate) fn build_drawing(primitives: &[Primitive]) -> Drawing {
+    let mut drawing = Drawing::new();
+
+    for primitive in primitives {
+        match primitive {
+            Primitive::Stroke { from, to, .. } => {
+                add_polyline(&mut drawing, vec![vertex(from.0, from.1, 0.0), vertex(to.0, to.1, 0.0)], false);
+            }
+            Primitive::StrokeArc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                clockwise,
+                ..
+            } => {
+                let swept = Gerber2SVG::swept_angle(*start_angle, *end_angle, *clockwise);
+                let start = (center.0 + radius * start_angle.cos(), center.1 + radius * start_angle.sin());
+                let end = (center.0 + radius * end_angle.cos(), center.1 + radius * end_angle.sin());
+                add_polyline(
+                    &mut drawing,
+                    vec![vertex(start.0, start.1, bulge(swept)), vertex(end.0, end.1, 0.0)],
+                    false,
+                );
+            }
+            Primitive::Circle { center, radius } => {
+                let circle = Circle {
+                    center: Point::new(center.0, center.1, 0.0),
+                    radius: *radius,
+                    ..Default::default()
+                };
+                drawing.add_entity(Entity::new(EntityType::Circle(circle)));
+            }
+            Primitive::Polygon { contour } => add_contour(&mut drawing, contour),
+            Primitive::Region { contours } => {
+                for contour in contours {
+                    add_contour(&mut drawing, contour);
+                }
+            }
+        }
+    }
+
+    drawing
+}
@@ -21,6 +21,9 @@ pub enum ImportError {
 pub enum ExportError {
     #[error("IO Error occrured: {0}")]
     IOError(std::io::Error),
+
+    #[error("DXF Error occrured: {0}")]
+    DxfError(String),
 }
 
 #[derive(Error, Debug)]
@@ -0,0 +1,187 @@
+//! Minimal Excellon (`.drl`) drill file parser, producing [`Primitive`]s (drilled holes as
+//! circles, routed slots as stroked centerline segments) that can be merged into a
+//! [`crate::Gerber2SVG`] layer via [`crate::Gerber2SVG::with_drill_holes`].
+//!
+//! This covers the common subset emitted by EDA tools (KiCad-style `METRIC`/`INCH` unit and
+//! `TZ`/`LZ` zero-suppression headers, a `;FILE_FORMAT=I:D` comment, `T<id>C<diameter>` tool
+//! definitions, plain `T<id>` tool selection, and `G00`/`G01` rout-mode slots) rather than the
+//! full Excellon command set (no tool-change stops, repeat blocks or canned cycles).
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use gerber_parser::gerber_types::Unit;
+
+use crate::error::ImportError;
+use crate::primitive::Primitive;
+
+#[derive(Debug, Clone, Copy)]
+struct CoordinateFormat {
+    integer_digits: usize,
+    decimal_digits: usize,
+    /// `true` if leading zeros are omitted from each coordinate (so the digits given are
+    /// right-aligned and must be padded on the left to reach the full width); `false` if
+    /// trailing zeros are the ones omitted instead (pad on the right).
+    leading_zero_suppression: bool,
+}
+
+impl Default for CoordinateFormat {
+    fn default() -> Self {
+        Self {
+            integer_digits: 3,
+            decimal_digits: 3,
+            leading_zero_suppression: true,
+        }
+    }
+}
+
+/// A parsed Excellon drill file: one hole/slot primitive per drill/rout command, plus the
+/// unit its coordinates are expressed in.
+#[derive(Debug)]
+pub struct ExcellonDrill {
+    pub(crate) unit: Unit,
+    pub(crate) primitives: Vec<Primitive>,
+}
+
+impl ExcellonDrill {
+    /// Parse an Excellon drill file.
+    /// * filename: `&str` path to the `.drl` file
+    pub fn from_excellon_file(filename: &str) -> Result<Self, ImportError> {
+        let file = File::open(filename).map_err(ImportError::IOError)?;
+        let reader = BufReader::new(file);
+
+        let mut unit = Unit::Millimeters;
+        let mut format = CoordinateFormat::default();
+        let mut tools: HashMap<u32, f64> = HashMap::new();
+        let mut selected_tool: Option<u32> = None;
+        let mut routing = false;
+        let mut route_start: Option<(f64, f64)> = None;
+        let mut primitives = Vec::new();
+
+        for line in reader.lines() {
+            let line = line.map_err(ImportError::IOError)?;
+            let mut line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix(';') {
+                if let Some(spec) = rest.strip_prefix("FILE_FORMAT=") {
+                    if let Some((i, d)) = spec.split_once(':') {
+                        if let (Ok(i), Ok(d)) = (i.trim().parse(), d.trim().parse()) {
+                            format.integer_digits = i;
+                            format.decimal_digits = d;
+                        }
+                    }
+                }
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix("METRIC") {
+                unit = Unit::Millimeters;
+                apply_zero_suppression(rest, &mut format);
+                continue;
+            }
+            if let Some(rest) = line.strip_prefix("INCH") {
+                unit = Unit::Inches;
+                apply_zero_suppression(rest, &mut format);
+                continue;
+            }
+
+            if matches!(line, "M48" | "M95" | "%" | "M30" | "M00") {
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix("G00") {
+                routing = true;
+                line = rest;
+            } else if let Some(rest) = line.strip_prefix("G01") {
+                line = rest;
+            } else if line.strip_prefix("G05").is_some() {
+                routing = false;
+                route_start = None;
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix('T') {
+                if let Some((id, diameter)) = parse_tool_definition(rest) {
+                    tools.insert(id, diameter);
+                } else if let Ok(id) = rest.trim_end_matches(|c: char| !c.is_ascii_digit()).parse() {
+                    selected_tool = Some(id);
+                }
+                continue;
+            }
+
+            if let Some((x, y)) = parse_coordinate_line(line, &format) {
+                let diameter = selected_tool.and_then(|id| tools.get(&id)).copied().unwrap_or(0.0);
+
+                if routing {
+                    if let Some(start) = route_start {
+                        primitives.push(Primitive::Stroke { from: start, to: (x, y), width: diameter });
+                    }
+                    route_start = Some((x, y));
+                } else {
+                    primitives.push(Primitive::Circle { center: (x, y), radius: diameter / 2.0 });
+                }
+            }
+        }
+
+        Ok(Self { unit, primitives })
+    }
+}
+
+fn apply_zero_suppression(header_rest: &str, format: &mut CoordinateFormat) {
+    if header_rest.contains("LZ") {
+        format.leading_zero_suppression = false;
+    } else if header_rest.contains("TZ") {
+        format.leading_zero_suppression = true;
+    }
+}
+
+/// Parse a `T<id>C<diameter>` tool definition, ignoring any trailing feed/speed fields
+/// (e.g. `T01C0.80F100S20000`).
+fn parse_tool_definition(rest: &str) -> Option<(u32, f64)> {
+    let c_pos = rest.find('C')?;
+    let id = rest[..c_pos].parse().ok()?;
+    let diameter = rest[c_pos + 1..].split(|c: char| c.is_ascii_alphabetic()).next()?.parse().ok()?;
+    Some((id, diameter))
+}
+
+fn parse_coordinate_line(line: &str, format: &CoordinateFormat) -> Option<(f64, f64)> {
+    let x_pos = line.find('X');
+    let y_pos = line.find('Y');
+
+    if x_pos.is_none() && y_pos.is_none() {
+        return None;
+    }
+
+    let x = x_pos.map(|i| parse_axis(&line[i + 1..], format)).unwrap_or(0.0);
+    let y = y_pos.map(|i| parse_axis(&line[i + 1..], format)).unwrap_or(0.0);
+
+    Some((x, y))
+}
+
+fn parse_axis(rest: &str, format: &CoordinateFormat) -> f64 {
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit() || *c == '-' || *c == '.').collect();
+
+    if digits.contains('.') {
+        return digits.parse().unwrap_or(0.0);
+    }
+
+    let negative = digits.starts_with('-');
+    let digits = digits.trim_start_matches('-');
+    let width = format.integer_digits + format.decimal_digits;
+    let padded = if format.leading_zero_suppression {
+        format!("{:0>width$}", digits, width = width)
+    } else {
+        format!("{:0<width$}", digits, width = width)
+    };
+
+    let value = padded.parse::<f64>().unwrap_or(0.0) / 10f64.powi(format.decimal_digits as i32);
+    if negative {
+        -value
+    } else {
+        value
+    }
+}
@@ -0,0 +1,2 @@
+pub mod point;
+pub(crate) mod shapes;
@@ -1,4 +1,4 @@
-use gerber_parser::gerber_types::{CoordinateFormat, CoordinateNumber, Coordinates};
+use gerber_parser::gerber_types::{CoordinateFormat, CoordinateNumber, CoordinateOffset, Coordinates};
 
 use crate::error::{ConversionError, Gerber2SvgError};
 
@@ -26,4 +26,17 @@ impl Point {
             None => default.clone(),
         }
     }
+
+    /// Extract the I/J offset pair carried by a `D01` circular interpolation, defaulting
+    /// any missing component to `0.0` (the offset is relative, unlike an absolute coordinate).
+    pub fn from_coordinate_offset(offset: &CoordinateOffset) -> (f64, f64) {
+        (
+            offset.x.map(|x| x.into()).unwrap_or(0.0),
+            offset.y.map(|y| y.into()).unwrap_or(0.0),
+        )
+    }
+
+    pub fn distance_to(&self, other: &Point) -> f64 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+    }
 }
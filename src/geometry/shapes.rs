@@ -0,0 +1,107 @@
+use svg::node::element::path;
+
+use super::point::Point;
+
+/// Append a stadium (rectangle with fully rounded ends) contour centered on `center`.
+/// Its corner radius is implicitly half of the smaller dimension, as required for an obround.
+pub(crate) fn append_stadium_contour(data: path::Data, center: &Point, width: f64, height: f64) -> path::Data {
+    let radius = f64::min(width, height) / 2.0;
+
+    if width >= height {
+        let straight = width / 2.0 - radius;
+        let left = center.x - straight;
+        let right = center.x + straight;
+        let top = center.y - radius;
+        let bottom = center.y + radius;
+
+        data.move_to((left, top))
+            .line_to((right, top))
+            .elliptical_arc_to((radius, radius, 0.0, 0, 1, right, bottom))
+            .line_to((left, bottom))
+            .elliptical_arc_to((radius, radius, 0.0, 0, 1, left, top))
+            .close()
+    } else {
+        let straight = height / 2.0 - radius;
+        let top = center.y - straight;
+        let bottom = center.y + straight;
+        let left = center.x - radius;
+        let right = center.x + radius;
+
+        data.move_to((right, top))
+            .elliptical_arc_to((radius, radius, 0.0, 0, 1, left, top))
+            .line_to((left, bottom))
+            .elliptical_arc_to((radius, radius, 0.0, 0, 1, right, bottom))
+            .line_to((right, top))
+            .close()
+    }
+}
+
+/// Append a circle contour, e.g. to cut a hole out of a pad via `fill-rule="evenodd"`
+/// (`reverse_winding` flips it against the outer contour it's cut out of).
+pub(crate) fn append_circle_contour(data: path::Data, center: &Point, radius: f64, reverse_winding: bool) -> path::Data {
+    let top = (center.x, center.y - radius);
+    let bottom = (center.x, center.y + radius);
+    let sweep_flag = if reverse_winding { 0 } else { 1 };
+
+    data.move_to(top)
+        .elliptical_arc_to((radius, radius, 0.0, 0, sweep_flag, bottom.0, bottom.1))
+        .elliptical_arc_to((radius, radius, 0.0, 0, sweep_flag, top.0, top.1))
+        .close()
+}
+
+/// Append a regular polygon contour of `vertices` sides, centered on `center`, with its
+/// first vertex placed at `rotation` radians (measured from the positive X axis).
+pub(crate) fn append_regular_polygon_contour(
+    data: path::Data,
+    center: &Point,
+    diameter: f64,
+    vertices: usize,
+    rotation: f64,
+) -> path::Data {
+    let radius = diameter / 2.0;
+
+    let points: Vec<(f64, f64)> = (0..vertices)
+        .map(|k| {
+            let angle = (2.0 * std::f64::consts::PI * k as f64) / vertices as f64 + rotation;
+            (center.x + radius * angle.cos(), center.y + radius * angle.sin())
+        })
+        .collect();
+
+    let mut data = data.move_to(points[0]);
+    for point in &points[1..] {
+        data = data.line_to(*point);
+    }
+
+    data.close()
+}
+
+/// Append a rectangle contour of `width`x`height` centered on `center` and rotated by
+/// `rotation` radians about that center.
+pub(crate) fn append_rotated_rectangle_contour(
+    data: path::Data,
+    center: &Point,
+    width: f64,
+    height: f64,
+    rotation: f64,
+) -> path::Data {
+    let hw = width / 2.0;
+    let hh = height / 2.0;
+
+    let corners = [(-hw, -hh), (hw, -hh), (hw, hh), (-hw, hh)];
+    let rotated: Vec<(f64, f64)> = corners
+        .iter()
+        .map(|(x, y)| {
+            (
+                center.x + x * rotation.cos() - y * rotation.sin(),
+                center.y + x * rotation.sin() + y * rotation.cos(),
+            )
+        })
+        .collect();
+
+    let mut data = data.move_to(rotated[0]);
+    for point in &rotated[1..] {
+        data = data.line_to(*point);
+    }
+
+    data.close()
+}
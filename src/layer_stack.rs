@@ -0,0 +1,87 @@
+//! Combine several independently-parsed [`Gerber2SVG`] layers into one multi-layer SVG,
+//! each one colored and rendered inside its own `<g>`, stacked bottom-to-top in add order,
+//! sharing one bounding box (e.g. copper + silkscreen + soldermask for the same board).
+use svg::node::element::Group;
+use svg::Document;
+
+use crate::error::{ExportError, Gerber2SvgError};
+use crate::Gerber2SVG;
+
+/// Stack of colored Gerber layers rendered into a single SVG document.
+#[derive(Debug, Default)]
+pub struct LayerStack {
+    layers: Vec<Group>,
+    unit_suffix: Option<&'static str>,
+    min_x: f64,
+    max_x: f64,
+    min_y: f64,
+    max_y: f64,
+}
+
+impl LayerStack {
+    /// Create an empty layer stack.
+    pub fn new() -> Self {
+        Self {
+            layers: Vec::new(),
+            unit_suffix: None,
+            min_x: f64::INFINITY,
+            max_x: f64::NEG_INFINITY,
+            min_y: f64::INFINITY,
+            max_y: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Add an already-[`build`](Gerber2SVG::build)'t layer on top of the stack, filled and
+    /// stroked with `color` at `opacity` (`0.0`-`1.0`). The stack's bounding box grows to
+    /// cover every layer added to it.
+    pub fn add_layer(mut self, mut gerber: Gerber2SVG, color: &str, opacity: f32) -> Self {
+        let group = gerber
+            .take_group()
+            .set("fill", color)
+            .set("stroke", color)
+            .set("opacity", opacity);
+
+        let (min_x, max_x, min_y, max_y) = gerber.bbox();
+        self.min_x = f64::min(self.min_x, min_x);
+        self.max_x = f64::max(self.max_x, max_x);
+        self.min_y = f64::min(self.min_y, min_y);
+        self.max_y = f64::max(self.max_y, max_y);
+        self.unit_suffix.get_or_insert(gerber.unit_suffix());
+
+        self.layers.push(group);
+
+        return self;
+    }
+
+    /// Save the merged layer stack as an SVG file.
+    /// * filename: `&str` path to save the SVG file
+    /// * crop: `bool` trim unused space
+    pub fn save_svg(&self, filename: &str, crop: bool) -> Result<(), Gerber2SvgError> {
+        svg::save(filename, &self.to_document(crop)).map_err(|x| ExportError::IOError(x).into())
+    }
+
+    /// Get the merged layer stack as an SVG string.
+    /// * crop: `bool` trim unused space
+    pub fn to_string(&self, crop: bool) -> String {
+        self.to_document(crop).to_string()
+    }
+
+    fn to_document(&self, crop: bool) -> Document {
+        let unit = self.unit_suffix.unwrap_or("mm");
+        let (width, height) = if crop {
+            (self.max_x - self.min_x, self.max_y - self.min_y)
+        } else {
+            (self.max_x, self.max_y)
+        };
+
+        let mut document = Document::new()
+            .set("width", format!("{}{}", width, unit))
+            .set("height", format!("{}{}", height, unit));
+
+        for layer in &self.layers {
+            document = document.add(layer.clone());
+        }
+
+        document
+    }
+}
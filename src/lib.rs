@@ -2,33 +2,59 @@ use std::fs::File;
 use std::io::BufReader;
 
 use gerber_parser::gerber_types::{self, Aperture, Command, GCode, InterpolationMode, Unit};
-use gerber_parser::gerber_types::{CoordinateOffset, FunctionCode};
+use gerber_parser::gerber_types::{CoordinateOffset, ExtendedCode, FunctionCode, Polarity, QuadrantMode};
 use gerber_parser::{parse, GerberDoc};
 
 use log::warn;
 use svg;
-use svg::node::element::{path, Circle, Path, Rectangle};
+use svg::node::element::{path, Circle, Group, Path, Rectangle};
 
 mod geometry;
 use geometry::point::Point;
+use geometry::shapes;
+
+mod dxf_export;
+mod macro_aperture;
+mod polarity;
+mod primitive;
+use primitive::Primitive;
 
 pub mod error;
+pub mod excellon;
+pub mod layer_stack;
 use crate::error::{ExportError, Gerber2SvgError, ImportError};
+use crate::excellon::ExcellonDrill;
 
-const SVG_COLOR_ELEMENT: &str = "black";
+const DEFAULT_COLOR: &str = "black";
 
 #[derive(Debug)]
 pub struct Gerber2SVG {
     gerber_doc: GerberDoc,
     unit: Unit,
     scale: f32,
+    color: String,
 
     draw_state: InterpolationMode,
+    quadrant_mode: QuadrantMode,
     position: Point,
     selected_aperture: Option<Aperture>,
 
-    svg_document: svg::Document,
+    region_mode: bool,
+    region_start_needed: bool,
+    region_path_data: path::Data,
+    region_current_contour: Vec<(f64, f64)>,
+    region_contours: Vec<Vec<(f64, f64)>>,
+
+    primitives: Vec<Primitive>,
+
+    polarity: Polarity,
+    exact_clipping: bool,
+    dark_polygons: Vec<Vec<(f64, f64)>>,
+    clear_polygons: Vec<Vec<(f64, f64)>>,
+
+    svg_group: Group,
     current_path_data: path::Data,
+    current_path_points: Vec<(f64, f64)>,
 
     min_x: f64,
     max_x: f64,
@@ -71,11 +97,24 @@ impl Gerber2SVG {
                 gerber_doc: gerber_doc,
                 unit,
                 scale: 1.0,
+                color: DEFAULT_COLOR.to_string(),
                 draw_state: InterpolationMode::Linear,
+                quadrant_mode: QuadrantMode::Multi,
                 position: Point::new(0.0, 0.0),
                 selected_aperture: None,
-                svg_document: svg::Document::new(),
+                region_mode: false,
+                region_start_needed: true,
+                region_path_data: path::Data::new(),
+                region_current_contour: Vec::new(),
+                region_contours: Vec::new(),
+                primitives: Vec::new(),
+                polarity: Polarity::Dark,
+                exact_clipping: false,
+                dark_polygons: Vec::new(),
+                clear_polygons: Vec::new(),
+                svg_group: Group::new(),
                 current_path_data: path::Data::new(),
+                current_path_points: Vec::new(),
                 min_x: f64::INFINITY,
                 max_x: f64::NEG_INFINITY,
                 min_y: f64::INFINITY,
@@ -96,19 +135,127 @@ impl Gerber2SVG {
         return self;
     }
 
+    /// Set the fill/stroke color used for every shape this layer draws when it is rendered on
+    /// its own via [`Self::save_svg`]/[`Self::to_string`] (default `"black"`). A
+    /// [`layer_stack::LayerStack`] does not use it: each layer there gets the color passed to
+    /// [`layer_stack::LayerStack::add_layer`].
+    pub fn set_color(mut self, color: &str) -> Self {
+        self.color = color.to_string();
+        return self;
+    }
+
+    /// Toggle exact `%LP` polarity clipping. When enabled, flashed pads are combined via
+    /// boolean polygon subtraction (`clipper2`) so clear-polarity flashes actually cut holes
+    /// out of dark geometry; when disabled (the default, for backwards compatibility),
+    /// polarity is ignored and layers are drawn with the original fast painter's algorithm.
+    /// Macro aperture flashes are never clipped: they are always painted directly.
+    pub fn with_exact_clipping(mut self, enable: bool) -> Self {
+        self.exact_clipping = enable;
+        return self;
+    }
+
+    /// Merge an Excellon drill file's holes into this layer (typically the copper layer the
+    /// drill file belongs to), extending the bounding box to cover them. When
+    /// [`Self::with_exact_clipping`] is enabled, holes are subtracted from the dark geometry
+    /// underneath via the same `dark - clear` pipeline as `%LP` clear-polarity flashes, so
+    /// they render as real cutouts; otherwise they're approximated by overlaying plain white
+    /// shapes, which only looks right against a white background.
+    pub fn with_drill_holes(mut self, drill: ExcellonDrill) -> Self {
+        let unit_scale = match (drill.unit, self.unit) {
+            (Unit::Millimeters, Unit::Inches) => 1.0 / 25.4,
+            (Unit::Inches, Unit::Millimeters) => 25.4,
+            _ => 1.0,
+        };
+
+        for hole in &drill.primitives {
+            match hole {
+                Primitive::Circle { center, radius } => {
+                    let center = (center.0 * unit_scale, center.1 * unit_scale);
+                    let radius = radius * unit_scale;
+                    self.check_bbox(center.0, center.1, radius, radius);
+
+                    if self.exact_clipping {
+                        self.clear_polygons.push(polarity::circle_contour(&Point::new(center.0, center.1), radius));
+                    } else {
+                        let circle = Circle::new()
+                            .set("cx", self.with_unit(center.0))
+                            .set("cy", self.with_unit(center.1))
+                            .set("r", radius)
+                            .set("fill", "white");
+                        self.svg_group = std::mem::replace(&mut self.svg_group, Group::new()).add(circle);
+                    }
+                }
+                Primitive::Stroke { from, to, width } => {
+                    let from = (from.0 * unit_scale, from.1 * unit_scale);
+                    let to = (to.0 * unit_scale, to.1 * unit_scale);
+                    let width = width * unit_scale;
+                    let half = width / 2.0;
+                    self.check_bbox(from.0, from.1, half, half);
+                    self.check_bbox(to.0, to.1, half, half);
+
+                    if self.exact_clipping {
+                        if let Some(contour) = polarity::inflate_stroke(&[from, to], width).into_iter().next() {
+                            self.clear_polygons.push(contour);
+                        }
+                    } else {
+                        let path = Path::new()
+                            .set("fill", "none")
+                            .set("stroke", "white")
+                            .set("stroke-width", self.with_unit(width))
+                            .set("d", path::Data::new().move_to(from).line_to(to));
+                        self.svg_group = std::mem::replace(&mut self.svg_group, Group::new()).add(path);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        self.primitives.extend(drill.primitives);
+
+        return self;
+    }
+
     /// Save the gerber as SVG file
     /// * filename: `&str` path to save the SVG file
     /// * crop: `bool` trim unused space
     pub fn save_svg(&mut self, filename: &str, crop: bool) -> Result<(), Gerber2SvgError> {
-        self.set_bbox(crop);
-        svg::save(filename, &self.svg_document).map_err(|x| ExportError::IOError(x).into())
+        svg::save(filename, &self.to_document(crop)).map_err(|x| ExportError::IOError(x).into())
     }
 
     /// Get SVG as String
     /// * crop: `bool` trim unused space
     pub fn to_string(&mut self, crop: bool) -> String {
-        self.set_bbox(crop);
-        self.svg_document.to_string()
+        self.to_document(crop).to_string()
+    }
+
+    /// Save the gerber as a DXF file for CAD/CAM import, built from the same shared
+    /// [`Primitive`] stream recorded while walking the command stream in [`Self::build`].
+    /// * filename: `&str` path to save the DXF file
+    pub fn save_dxf(&mut self, filename: &str) -> Result<(), Gerber2SvgError> {
+        dxf_export::build_drawing(&self.primitives)
+            .save_file(filename)
+            .map_err(|x| ExportError::DxfError(x.to_string()).into())
+    }
+
+    /// Wrap the rendered group in a standalone `<svg>` document sized to the bounding box.
+    fn to_document(&self, crop: bool) -> svg::Document {
+        let (width, height) = if crop {
+            (self.max_x - self.min_x, self.max_y - self.min_y)
+        } else {
+            (self.max_x, self.max_y)
+        };
+
+        // Shapes no longer carry their own fill/stroke (see `take_group`), so the color is
+        // applied once here, at the group that wraps the whole layer.
+        let mut group = self.svg_group.clone().set("fill", self.color.as_str()).set("stroke", self.color.as_str());
+        if let Some(path) = self.resolved_polarity_path() {
+            group = group.add(path);
+        }
+
+        svg::Document::new()
+            .set("width", self.with_unit(width))
+            .set("height", self.with_unit(height))
+            .add(group)
     }
 
     /// Build the SVG
@@ -134,15 +281,16 @@ impl Gerber2SVG {
                                 let target =
                                     Point::from_coordinates(coord.clone().unwrap(), &self.position);
 
-                                if self.draw_state == InterpolationMode::Linear {
+                                if self.draw_state != InterpolationMode::Linear && offset.is_none() {
+                                    warn!("Offset is required in Counter/Clockwise Circular mode");
+                                    continue;
+                                }
+
+                                if self.region_mode {
+                                    self.add_region_segment(&target, offset.as_ref());
+                                } else if self.draw_state == InterpolationMode::Linear {
                                     self.add_draw_segment(&target);
                                 } else {
-                                    if offset.is_none() {
-                                        warn!(
-                                            "Offset is required in Counter/Clockwise Circular mode"
-                                        );
-                                        continue;
-                                    }
                                     self.add_arc_segment(&target, offset.as_ref().unwrap())
                                 }
 
@@ -155,8 +303,15 @@ impl Gerber2SVG {
                                 }
                                 let target =
                                     Point::from_coordinates(m.clone().unwrap(), &self.position);
-                                log::debug!("Move to {:?}, create path.", target);
-                                self.create_path_from_data();
+
+                                if self.region_mode {
+                                    log::debug!("Move to {:?}, close region sub-contour.", target);
+                                    self.close_region_subpath();
+                                } else {
+                                    log::debug!("Move to {:?}, create path.", target);
+                                    self.create_path_from_data();
+                                }
+
                                 self.move_position(&target);
                             }
                             gerber_types::Operation::Flash(f) => {
@@ -179,12 +334,18 @@ impl Gerber2SVG {
                     },
                     FunctionCode::GCode(g) => match g {
                         GCode::InterpolationMode(im) => self.draw_state = im,
+                        GCode::QuadrantMode(qm) => self.quadrant_mode = qm,
+                        GCode::RegionMode(true) => self.begin_region(),
+                        GCode::RegionMode(false) => self.end_region(),
                         GCode::Comment(c) => log::info!("[COMMENT] \"{:?}\"", c),
                         _ => log::error!("Unsupported GCode:\r\n{:#?}", g),
                     },
                     FunctionCode::MCode(_) => (),
                 },
-                Command::ExtendedCode(_) => (),
+                Command::ExtendedCode(e) => match e {
+                    ExtendedCode::LoadPolarity(p) => self.polarity = p,
+                    _ => (),
+                },
             };
         }
 
@@ -198,7 +359,7 @@ impl Gerber2SVG {
         //     target.1.unwrap_or(self.position.y),
         // );
 
-        let mut doc = std::mem::replace(&mut self.svg_document, svg::Document::new());
+        let mut doc = std::mem::replace(&mut self.svg_group, Group::new());
 
         log::debug!(
             "Place aperture {:?} to {:?}",
@@ -213,37 +374,279 @@ impl Gerber2SVG {
         {
             Aperture::Circle(c) => {
                 let radius = (c.diameter / 2.0) * self.scale as f64;
-                let circle = Circle::new()
-                    .set("cx", self.with_unit(target.x))
-                    .set("cy", self.with_unit(target.y))
-                    .set("r", radius)
-                    .set("fill", SVG_COLOR_ELEMENT);
-                doc = doc.add(circle);
+
+                if self.exact_clipping {
+                    self.push_polarity_contour(polarity::circle_contour(target, radius));
+                } else {
+                    let circle = Circle::new()
+                        .set("cx", self.with_unit(target.x))
+                        .set("cy", self.with_unit(target.y))
+                        .set("r", radius);
+                    doc = doc.add(circle);
+                }
                 self.check_bbox(target.x, target.y, radius, radius);
+                self.primitives.push(Primitive::Circle { center: (target.x, target.y), radius });
             }
             Aperture::Rectangle(r) => {
                 let width = r.x * self.scale as f64;
                 let height = r.y * self.scale as f64;
-                let x = target.x - width / 2.0;
-                let y = target.y - height / 2.0;
-
-                let rect = Rectangle::new()
-                    .set("x", self.with_unit(x))
-                    .set("y", self.with_unit(y))
-                    .set("width", self.with_unit(width))
-                    .set("height", self.with_unit(height))
-                    .set("fill", SVG_COLOR_ELEMENT);
-                doc = doc.add(rect);
+
+                if self.exact_clipping {
+                    self.push_polarity_contour(polarity::rectangle_contour(target, width, height));
+                } else {
+                    let x = target.x - width / 2.0;
+                    let y = target.y - height / 2.0;
+
+                    let rect = Rectangle::new()
+                        .set("x", self.with_unit(x))
+                        .set("y", self.with_unit(y))
+                        .set("width", self.with_unit(width))
+                        .set("height", self.with_unit(height));
+                    doc = doc.add(rect);
+                }
+                self.check_bbox(target.x, target.y, width / 2.0, height / 2.0);
+                self.primitives.push(Primitive::Polygon {
+                    contour: polarity::rectangle_contour(target, width, height),
+                });
+            }
+            Aperture::Obround(o) => {
+                let width = o.x * self.scale as f64;
+                let height = o.y * self.scale as f64;
+                let hole_diameter = o.hole_diameter;
+
+                // The outer stadium outline as a polygon: the centerline between the two end
+                // caps, inflated by half the smaller dimension.
+                let half_span = width.max(height) / 2.0 - width.min(height) / 2.0;
+                let centerline = if width >= height {
+                    [(target.x - half_span, target.y), (target.x + half_span, target.y)]
+                } else {
+                    [(target.x, target.y - half_span), (target.x, target.y + half_span)]
+                };
+                let stadium = polarity::inflate_stroke(&centerline, width.min(height)).into_iter().next();
+
+                if self.exact_clipping {
+                    // Holes aren't fed into the exact-clipping pipeline; the pad is
+                    // approximated as its outer stadium contour only.
+                    if let Some(contour) = stadium.clone() {
+                        self.push_polarity_contour(contour);
+                    }
+                } else {
+                    let mut data = shapes::append_stadium_contour(path::Data::new(), target, width, height);
+                    let mut path = Path::new();
+
+                    if let Some(hole_diameter) = hole_diameter.filter(|d| *d > 0.0) {
+                        let hole_radius = (hole_diameter / 2.0) * self.scale as f64;
+                        data = shapes::append_circle_contour(data, target, hole_radius, true);
+                        path = path.set("fill-rule", "evenodd");
+                    }
+
+                    doc = doc.add(path.set("d", data));
+                }
                 self.check_bbox(target.x, target.y, width / 2.0, height / 2.0);
+
+                // Same approximation as the exact-clipping branch above: the DXF primitive
+                // captures the outer stadium outline only, not the hole.
+                if let Some(contour) = stadium {
+                    self.primitives.push(Primitive::Polygon { contour });
+                }
+            }
+            Aperture::Polygon(p) => {
+                let diameter = p.diameter * self.scale as f64;
+                let rotation = p.rotation.unwrap_or(0.0).to_radians();
+                let vertices = p.vertices as usize;
+                let hole_diameter = p.hole_diameter;
+
+                if self.exact_clipping {
+                    self.push_polarity_contour(polarity::circle_contour(target, diameter / 2.0));
+                } else {
+                    let mut data = shapes::append_regular_polygon_contour(path::Data::new(), target, diameter, vertices, rotation);
+                    let mut path = Path::new();
+
+                    if let Some(hole_diameter) = hole_diameter.filter(|d| *d > 0.0) {
+                        let hole_radius = (hole_diameter / 2.0) * self.scale as f64;
+                        data = shapes::append_circle_contour(data, target, hole_radius, true);
+                        path = path.set("fill-rule", "evenodd");
+                    }
+
+                    doc = doc.add(path.set("d", data));
+                }
+                self.check_bbox(target.x, target.y, diameter / 2.0, diameter / 2.0);
+
+                let radius = diameter / 2.0;
+                let contour: Vec<(f64, f64)> = (0..vertices)
+                    .map(|k| {
+                        let angle = (2.0 * std::f64::consts::PI * k as f64) / vertices as f64 + rotation;
+                        (target.x + radius * angle.cos(), target.y + radius * angle.sin())
+                    })
+                    .collect();
+                self.primitives.push(Primitive::Polygon { contour });
+            }
+            Aperture::Macro(macro_name, macro_args) => {
+                let macro_name = macro_name.clone();
+                let macro_args = macro_args.clone();
+                let data = self.expand_macro_aperture(&macro_name, &macro_args, target);
+
+                match data {
+                    Some(data) => {
+                        let path = Path::new().set("fill-rule", "evenodd").set("d", data);
+                        doc = doc.add(path);
+                        // Macro apertures aren't fed into the shared primitive stream yet:
+                        // each macro primitive type would need its own DXF translation, so
+                        // for now they only ever reach the SVG backend.
+                    }
+                    None => log::error!("Unknown aperture macro '{macro_name}'"),
+                }
             }
-            Aperture::Obround(o) => log::error!("Unsupported Obround aperture:\r\n{o:#?}"),
-            Aperture::Polygon(p) => log::error!("Unsupported Polygon aperture:\r\n{p:#?}"),
-            Aperture::Macro(macro_str, macro_decimals) => {
-                log::error!("Unsupported Macro aperture:\r\n{macro_str} -- {macro_decimals:#?}")
+        }
+
+        self.svg_group = doc;
+    }
+
+    /// Record a flashed pad's contour under the current `%LP` polarity instead of drawing
+    /// it immediately; only used when exact clipping is enabled.
+    fn push_polarity_contour(&mut self, contour: Vec<(f64, f64)>) {
+        match self.polarity {
+            Polarity::Dark => self.dark_polygons.push(contour),
+            Polarity::Clear => self.clear_polygons.push(contour),
+        }
+    }
+
+    /// When exact clipping is enabled, compute `dark - clear` over every polygon recorded by
+    /// [`Self::push_polarity_contour`] (including any drill holes merged in later via
+    /// [`Self::with_drill_holes`]) and build it as a single fill path. Computed fresh from
+    /// `self.dark_polygons`/`self.clear_polygons` on every call rather than cached, so it
+    /// always reflects the latest holes without re-walking the command stream.
+    fn resolved_polarity_path(&self) -> Option<Path> {
+        if !self.exact_clipping {
+            return None;
+        }
+
+        let resolved = polarity::subtract(&self.dark_polygons, &self.clear_polygons);
+
+        let mut data = path::Data::new();
+        for contour in &resolved {
+            if contour.is_empty() {
+                continue;
+            }
+            data = data.move_to(contour[0]);
+            for point in &contour[1..] {
+                data = data.line_to(*point);
+            }
+            data = data.close();
+        }
+
+        Some(Path::new().set("fill-rule", "evenodd").set("d", data))
+    }
+
+    /// Instantiate a flashed `%AM` macro aperture's primitives at `target`, scaled by
+    /// `self.scale`, and feed the flash's reach back into the bounding box.
+    fn expand_macro_aperture(
+        &mut self,
+        macro_name: &str,
+        macro_args: &Option<Vec<f64>>,
+        target: &Point,
+    ) -> Option<path::Data> {
+        let definition = self.gerber_doc.aperture_macros.get(macro_name)?.clone();
+        let args = macro_args.clone().unwrap_or_default();
+
+        let (data, max_extent) =
+            macro_aperture::expand(&definition.primitives, &args, target, self.scale as f64);
+        self.check_bbox(target.x, target.y, max_extent, max_extent);
+
+        Some(data)
+    }
+
+    /// Enter `G36` contour mode: flush any pending stroked path, then start accumulating
+    /// subsequent `D01` interpolations into an unstroked region contour instead.
+    fn begin_region(&mut self) {
+        self.create_path_from_data();
+        self.region_mode = true;
+        self.region_start_needed = true;
+        self.region_path_data = path::Data::new();
+        self.region_current_contour = Vec::new();
+        self.region_contours = Vec::new();
+    }
+
+    /// Leave contour mode on `G37`, emitting everything accumulated since `G36` as a
+    /// single filled (not stroked) path, closing every sub-contour it contains.
+    fn end_region(&mut self) {
+        self.close_region_subpath();
+
+        let data = std::mem::replace(&mut self.region_path_data, path::Data::new());
+        let contours = std::mem::take(&mut self.region_contours);
+
+        if self.exact_clipping {
+            // Feed the region's contour(s) into the same dark/clear polygon set as flashed
+            // pads, so a clear-polarity region actually cuts a hole out of the geometry
+            // underneath it instead of only ever painting on top. Regions with more than one
+            // sub-contour of the same polarity are assumed non-overlapping (the common case);
+            // a sub-contour meant as a cut-out within the region itself isn't tracked here.
+            for contour in &contours {
+                self.push_polarity_contour(contour.clone());
             }
+        } else if !data.is_empty() {
+            let svg = std::mem::replace(&mut self.svg_group, Group::new());
+            let path = Path::new().set("fill-rule", "nonzero").set("stroke", "none").set("d", data);
+            self.svg_group = svg.add(path);
+        }
+
+        if !contours.is_empty() {
+            self.primitives.push(Primitive::Region { contours });
+        }
+
+        self.region_mode = false;
+    }
+
+    /// Close the region's current sub-contour (a `D02` move inside `G36`/`G37` starts a new
+    /// one) so the next `D01` segment begins with its own `move_to` rather than a stray line.
+    fn close_region_subpath(&mut self) {
+        let data = std::mem::take(&mut self.region_path_data);
+        self.region_path_data = if data.is_empty() { data } else { data.close() };
+        self.region_start_needed = true;
+
+        let contour = std::mem::take(&mut self.region_current_contour);
+        if !contour.is_empty() {
+            self.region_contours.push(contour);
+        }
+    }
+
+    /// Append a `D01` interpolation (linear or circular) to the current region contour,
+    /// in `G36`/`G37` contour mode, without emitting a stroked path for it.
+    fn add_region_segment(&mut self, target: &Point, offset: Option<&CoordinateOffset>) -> () {
+        let start = self.position.clone();
+        let mut data = std::mem::take(&mut self.region_path_data);
+
+        if self.region_start_needed {
+            data = data.move_to((start.x, start.y));
+            self.region_current_contour.push((start.x, start.y));
+            self.region_start_needed = false;
         }
 
-        self.svg_document = doc;
+        if self.draw_state == InterpolationMode::Linear {
+            data = data.line_to((target.x, target.y));
+            self.check_bbox(target.x, target.y, 0.0, 0.0);
+            self.region_current_contour.push((target.x, target.y));
+        } else {
+            // The caller already warned and skipped the operation if `offset` is missing here.
+            let offset = offset.unwrap();
+            let (center, radius) = self.resolve_arc_center(&start, target, offset);
+            let clockwise = self.draw_state == InterpolationMode::ClockwiseCircular;
+            let sweep_flag = Self::svg_sweep_flag(clockwise);
+
+            let a0 = (start.y - center.y).atan2(start.x - center.x);
+            let a1 = (target.y - center.y).atan2(target.x - center.x);
+            let swept = Self::swept_angle(a0, a1, clockwise);
+            let large_arc_flag = if swept.abs() > std::f64::consts::PI { 1 } else { 0 };
+
+            data = data.elliptical_arc_to((radius, radius, 0.0, large_arc_flag, sweep_flag, target.x, target.y));
+            self.check_arc_bbox(&center, radius, a0, a1, clockwise, 0.0);
+
+            // Approximate the arc with a short run of points for the DXF/polarity-facing
+            // contour; the SVG path above still renders it as an exact elliptical arc.
+            self.region_current_contour.extend(Self::sample_arc(&center, radius, a0, swept));
+        }
+
+        self.region_path_data = data;
     }
 
     fn add_draw_segment(&mut self, target: &Point) -> () {
@@ -253,17 +656,219 @@ impl Gerber2SVG {
 
         if path.is_empty() {
             path = path.move_to((self.position.x, self.position.y));
+            self.current_path_points.push((self.position.x, self.position.y));
         }
 
         self.current_path_data = path.line_to((target.x, target.y));
+        self.current_path_points.push((target.x, target.y));
 
         let stroke = self.get_path_stroke();
         self.check_bbox(target.x, target.y, stroke / 2.0, stroke / 2.0);
+        self.primitives.push(Primitive::Stroke {
+            from: (self.position.x, self.position.y),
+            to: (target.x, target.y),
+            width: stroke,
+        });
+    }
+
+    fn add_arc_segment(&mut self, target: &Point, offset: &CoordinateOffset) -> () {
+        let start = self.position.clone();
+
+        log::debug!("Draw arc from {:?} to {:?} (offset {:?})", start, target, offset);
+
+        let (center, radius) = self.resolve_arc_center(&start, target, offset);
+        let clockwise = self.draw_state == InterpolationMode::ClockwiseCircular;
+        let sweep_flag = Self::svg_sweep_flag(clockwise);
+
+        let a0 = (start.y - center.y).atan2(start.x - center.x);
+        let a1 = (target.y - center.y).atan2(target.x - center.x);
+        let half_stroke = self.get_path_stroke() / 2.0;
+
+        let mut path = std::mem::take(&mut self.current_path_data);
+        if path.is_empty() {
+            path = path.move_to((start.x, start.y));
+            self.current_path_points.push((start.x, start.y));
+        }
+
+        if start.x == target.x && start.y == target.y {
+            // A flash-less closed circle: start == target, so split it into two
+            // semicircles since a single SVG arc command cannot express a full turn.
+            let mid = Point::new(2.0 * center.x - start.x, 2.0 * center.y - start.y);
+            path = path.elliptical_arc_to((radius, radius, 0.0, 0, sweep_flag, mid.x, mid.y));
+            path = path.elliptical_arc_to((radius, radius, 0.0, 0, sweep_flag, target.x, target.y));
+
+            let a_mid = a0 + std::f64::consts::PI;
+            self.check_arc_bbox(&center, radius, a0, a_mid, clockwise, half_stroke);
+            self.check_arc_bbox(&center, radius, a_mid, a0, clockwise, half_stroke);
+            self.current_path_points
+                .extend(Self::sample_arc(&center, radius, a0, Self::swept_angle(a0, a_mid, clockwise)));
+            self.current_path_points
+                .extend(Self::sample_arc(&center, radius, a_mid, Self::swept_angle(a_mid, a0, clockwise)));
+
+            let center_xy = (center.x, center.y);
+            self.primitives.push(Primitive::StrokeArc {
+                center: center_xy,
+                radius,
+                start_angle: a0,
+                end_angle: a_mid,
+                clockwise,
+                width: 2.0 * half_stroke,
+            });
+            self.primitives.push(Primitive::StrokeArc {
+                center: center_xy,
+                radius,
+                start_angle: a_mid,
+                end_angle: a0,
+                clockwise,
+                width: 2.0 * half_stroke,
+            });
+        } else {
+            let swept = Self::swept_angle(a0, a1, clockwise);
+            let large_arc_flag = if swept.abs() > std::f64::consts::PI { 1 } else { 0 };
+
+            path = path.elliptical_arc_to((radius, radius, 0.0, large_arc_flag, sweep_flag, target.x, target.y));
+            self.check_arc_bbox(&center, radius, a0, a1, clockwise, half_stroke);
+            self.current_path_points.extend(Self::sample_arc(&center, radius, a0, swept));
+
+            self.primitives.push(Primitive::StrokeArc {
+                center: (center.x, center.y),
+                radius,
+                start_angle: a0,
+                end_angle: a1,
+                clockwise,
+                width: 2.0 * half_stroke,
+            });
+        }
+
+        self.current_path_data = path;
+
+        let stroke = self.get_path_stroke();
+        self.check_bbox(target.x, target.y, stroke / 2.0, stroke / 2.0);
+    }
+
+    /// Resolve the arc center and radius for the current quadrant mode.
+    /// In `G75` (multi-quadrant) I/J are signed offsets from `start`. In `G74`
+    /// (single-quadrant) I/J are unsigned, so the correct center is picked among
+    /// the four sign combinations as the one equidistant from `start` and `target`.
+    fn resolve_arc_center(&self, start: &Point, target: &Point, offset: &CoordinateOffset) -> (Point, f64) {
+        let (i, j) = Point::from_coordinate_offset(offset);
+
+        match self.quadrant_mode {
+            QuadrantMode::Multi => {
+                let center = Point::new(start.x + i, start.y + j);
+                let radius = start.distance_to(&center);
+                (center, radius)
+            }
+            QuadrantMode::Single => {
+                let mut best: Option<(Point, f64)> = None;
+                let mut best_err = f64::INFINITY;
+
+                for (sx, sy) in [(1.0, 1.0), (1.0, -1.0), (-1.0, 1.0), (-1.0, -1.0)] {
+                    let center = Point::new(start.x + i * sx, start.y + j * sy);
+                    let r_start = start.distance_to(&center);
+                    let r_target = target.distance_to(&center);
+                    let err = (r_start - r_target).abs();
+
+                    if err < best_err {
+                        best_err = err;
+                        best = Some((center, (r_start + r_target) / 2.0));
+                    }
+                }
+
+                best.expect("Single-quadrant arc requires a valid I/J offset")
+            }
+        }
+    }
+
+    /// SVG's sweep flag is defined in a y-down coordinate system, which is the opposite
+    /// winding of the Gerber plane, so clockwise/counterclockwise swap: `0` for a Gerber
+    /// clockwise (`G02`) arc, `1` for counterclockwise (`G03`).
+    pub(crate) fn svg_sweep_flag(clockwise: bool) -> u32 {
+        if clockwise {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Number of straight segments used to approximate an arc as a polyline for contexts
+    /// that need actual points rather than an SVG arc command (DXF export, polarity clipping).
+    const ARC_CONTOUR_SEGMENTS: usize = 16;
+
+    /// Sample points along an arc swept from `a0` by `swept` radians around `center`,
+    /// excluding the start point (the caller is expected to already have it).
+    fn sample_arc(center: &Point, radius: f64, a0: f64, swept: f64) -> Vec<(f64, f64)> {
+        (1..=Self::ARC_CONTOUR_SEGMENTS)
+            .map(|s| {
+                let angle = a0 + swept * (s as f64 / Self::ARC_CONTOUR_SEGMENTS as f64);
+                (center.x + radius * angle.cos(), center.y + radius * angle.sin())
+            })
+            .collect()
+    }
+
+    /// Signed angle swept going from `a0` to `a1` in the given direction, in `(-2π, 2π)`.
+    pub(crate) fn swept_angle(a0: f64, a1: f64, clockwise: bool) -> f64 {
+        let two_pi = std::f64::consts::TAU;
+        let mut delta = if clockwise { a0 - a1 } else { a1 - a0 };
+
+        while delta < 0.0 {
+            delta += two_pi;
+        }
+
+        if clockwise {
+            -delta
+        } else {
+            delta
+        }
     }
 
-    fn add_arc_segment(&mut self, _target: &Point, _offset: &CoordinateOffset) -> () {
-        log::warn!("Arc are not supported ! Skip.",);
-        //TODO : self.check_bbox(...);
+    /// Whether the axis-aligned angle `theta` falls inside the arc swept from `a0` to `a1`.
+    fn angle_in_sweep(a0: f64, a1: f64, clockwise: bool, theta: f64) -> bool {
+        let two_pi = std::f64::consts::TAU;
+        let norm = |a: f64| ((a % two_pi) + two_pi) % two_pi;
+
+        let a0n = norm(a0);
+        let thetan = norm(theta);
+
+        if clockwise {
+            let mut a1n = norm(a1);
+            if a1n > a0n {
+                a1n -= two_pi;
+            }
+            let mut t = thetan;
+            if t > a0n {
+                t -= two_pi;
+            }
+            t <= a0n && t >= a1n
+        } else {
+            let mut a1n = norm(a1);
+            if a1n < a0n {
+                a1n += two_pi;
+            }
+            let mut t = thetan;
+            if t < a0n {
+                t += two_pi;
+            }
+            t >= a0n && t <= a1n
+        }
+    }
+
+    /// Extend the bounding box with every axis-crossing point of an arc that falls
+    /// inside its swept range, in addition to its (already tracked) endpoints.
+    /// `half` is half the stroke width to pad by (`0.0` for an unstroked region contour).
+    fn check_arc_bbox(&mut self, center: &Point, radius: f64, a0: f64, a1: f64, clockwise: bool, half: f64) {
+        for theta in [
+            0.0,
+            std::f64::consts::FRAC_PI_2,
+            std::f64::consts::PI,
+            -std::f64::consts::FRAC_PI_2,
+        ] {
+            if Self::angle_in_sweep(a0, a1, clockwise, theta) {
+                let x = center.x + radius * theta.cos();
+                let y = center.y + radius * theta.sin();
+                self.check_bbox(x, y, half, half);
+            }
+        }
     }
 
     fn move_position(&mut self, coord: &Point) -> () {
@@ -284,15 +889,23 @@ impl Gerber2SVG {
         }
 
         let data = std::mem::replace(&mut self.current_path_data, path::Data::new());
-        let svg = std::mem::replace(&mut self.svg_document, svg::Document::new());
+        let points = std::mem::take(&mut self.current_path_points);
 
-        let path = Path::new()
-            .set("fill", "none")
-            .set("stroke", SVG_COLOR_ELEMENT)
-            .set("stroke-width", self.with_unit(stroke))
-            .set("d", data);
+        if self.exact_clipping {
+            // Offset the centerline by half the stroking aperture's diameter so the trace
+            // takes part in the same `dark - clear` clipping as flashed pads and regions.
+            for contour in polarity::inflate_stroke(&points, stroke) {
+                self.push_polarity_contour(contour);
+            }
+        } else {
+            let svg = std::mem::replace(&mut self.svg_group, Group::new());
+            let path = Path::new()
+                .set("fill", "none")
+                .set("stroke-width", self.with_unit(stroke))
+                .set("d", data);
 
-        self.svg_document = svg.add(path);
+            self.svg_group = svg.add(path);
+        }
     }
 
     fn get_path_stroke(&self) -> f64 {
@@ -383,50 +996,74 @@ impl Gerber2SVG {
         self.max_y = f64::max(pos_y + stroke_y, self.max_y);
     }
 
-    fn set_bbox(&mut self, crop: bool) {
-        let mut doc = std::mem::replace(&mut self.svg_document, svg::Document::new());
-
-        if crop {
-            log::info!("Crop enable");
-            doc = doc
-                // .set(
-                //     "viewbox",
-                //     (
-                //         format!("{}{}", self.min_x, unit),
-                //         format!("{}{}", self.min_y, unit),
-                //         format!("{}{}", self.max_x - self.min_x, unit),
-                //         format!("{}{}", self.max_y - self.min_y, unit),
-                //     ),
-                // )
-                .set("width", self.with_unit(self.max_x - self.min_x))
-                .set("height", self.with_unit(self.max_y - self.min_y));
-        } else {
-            log::debug!("Crop disable");
-            doc = doc
-                // .set(
-                //     "viewbox",
-                //     (
-                //         0,
-                //         0,
-                //         format!("{}{}", self.max_x, unit),
-                //         format!("{}{}", self.max_y, unit),
-                //     ),
-                // )
-                .set("width", self.with_unit(self.max_x))
-                .set("height", self.with_unit(self.max_y));
+    /// Take ownership of the rendered shapes, leaving an empty group behind. Used by
+    /// [`layer_stack::LayerStack`] to re-home a layer's content under its own `<g>`. Shapes
+    /// carry no fill/stroke of their own (see [`Self::to_document`]), so the caller's own
+    /// `fill`/`stroke` on the `<g>` it wraps this in actually governs their color.
+    pub(crate) fn take_group(&mut self) -> Group {
+        let group = std::mem::replace(&mut self.svg_group, Group::new());
+        match self.resolved_polarity_path() {
+            Some(path) => group.add(path),
+            None => group,
         }
+    }
 
-        self.svg_document = doc;
+    /// The layer's bounding box in its native unit, as `(min_x, max_x, min_y, max_y)`.
+    pub(crate) fn bbox(&self) -> (f64, f64, f64, f64) {
+        (self.min_x, self.max_x, self.min_y, self.max_y)
+    }
+
+    pub(crate) fn unit_suffix(&self) -> &'static str {
+        match self.unit {
+            Unit::Inches => "in",
+            Unit::Millimeters => "mm",
+        }
     }
 
     fn with_unit(&self, val: f64) -> String {
-        format!(
-            "{}{}",
-            val,
-            match self.unit {
-                Unit::Inches => "in",
-                Unit::Millimeters => "mm",
-            }
-        )
+        format!("{}{}", val, self.unit_suffix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+    #[test]
+    fn svg_sweep_flag_swaps_for_y_down_coordinates() {
+        // G02 (clockwise in the Gerber plane) must become the SVG sweep flag for
+        // counterclockwise-in-y-down, i.e. `0`; G03 is the opposite, `1`.
+        assert_eq!(Gerber2SVG::svg_sweep_flag(true), 0);
+        assert_eq!(Gerber2SVG::svg_sweep_flag(false), 1);
+    }
+
+    #[test]
+    fn swept_angle_orientation_matches_direction() {
+        // A quarter turn counterclockwise from 0 to +90deg is a positive sweep...
+        assert!((Gerber2SVG::swept_angle(0.0, FRAC_PI_2, false) - FRAC_PI_2).abs() < 1e-9);
+        // ...while the same two angles swept clockwise go the long way around, negatively.
+        let clockwise = Gerber2SVG::swept_angle(0.0, FRAC_PI_2, true);
+        assert!((clockwise + (TAU - FRAC_PI_2)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn angle_in_sweep_counterclockwise_axis_crossings() {
+        // A counterclockwise arc from 0 to PI sweeps through +90deg but not -90deg, and
+        // includes both of its endpoints (0deg and 180deg).
+        assert!(Gerber2SVG::angle_in_sweep(0.0, PI, false, FRAC_PI_2));
+        assert!(!Gerber2SVG::angle_in_sweep(0.0, PI, false, -FRAC_PI_2));
+        assert!(Gerber2SVG::angle_in_sweep(0.0, PI, false, 0.0));
+        assert!(Gerber2SVG::angle_in_sweep(0.0, PI, false, PI));
+    }
+
+    #[test]
+    fn angle_in_sweep_clockwise_axis_crossings() {
+        // A clockwise arc from 0 to PI (the short way, through -90deg) sweeps through
+        // -90deg/180deg but not +90deg.
+        assert!(Gerber2SVG::angle_in_sweep(0.0, PI, true, -FRAC_PI_2));
+        assert!(!Gerber2SVG::angle_in_sweep(0.0, PI, true, FRAC_PI_2));
+        assert!(Gerber2SVG::angle_in_sweep(0.0, PI, true, 0.0));
+        assert!(Gerber2SVG::angle_in_sweep(0.0, PI, true, PI));
     }
 }
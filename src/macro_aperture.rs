@@ -0,0 +1,418 @@
+//! Expansion of `%AM` aperture macros into flashable SVG path contours.
+use svg::node::element::path;
+
+use crate::geometry::point::Point;
+use crate::geometry::shapes;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Plus,
+    Minus,
+    Mul,
+    Div,
+}
+
+/// Evaluate a macro modifier expression (e.g. `"$1+0.5"`, `"$2x2"`) against the flash's
+/// `$1..$n` arguments. Gerber macro arithmetic follows standard precedence: `x`/`/` bind
+/// tighter than `+`/`-`, left to right within a level, with parentheses overriding the order.
+pub(crate) fn eval_modifier(expr: &str, args: &[f64]) -> f64 {
+    let bytes: Vec<char> = expr.trim().chars().collect();
+    let mut pos = 0usize;
+    parse_expr(&bytes, &mut pos, args)
+}
+
+fn parse_value(bytes: &[char], pos: &mut usize, args: &[f64]) -> f64 {
+    let mut sign = 1.0;
+    while *pos < bytes.len() && (bytes[*pos] == '+' || bytes[*pos] == '-') {
+        if bytes[*pos] == '-' {
+            sign *= -1.0;
+        }
+        *pos += 1;
+    }
+
+    if *pos < bytes.len() && bytes[*pos] == '(' {
+        *pos += 1;
+        let value = parse_expr(bytes, pos, args);
+        if *pos < bytes.len() && bytes[*pos] == ')' {
+            *pos += 1;
+        }
+        return sign * value;
+    }
+
+    if *pos < bytes.len() && bytes[*pos] == '$' {
+        *pos += 1;
+        let start = *pos;
+        while *pos < bytes.len() && bytes[*pos].is_ascii_digit() {
+            *pos += 1;
+        }
+        let index: usize = bytes[start..*pos].iter().collect::<String>().parse().unwrap_or(1);
+        return sign * args.get(index - 1).copied().unwrap_or(0.0);
+    }
+
+    let start = *pos;
+    while *pos < bytes.len() && (bytes[*pos].is_ascii_digit() || bytes[*pos] == '.') {
+        *pos += 1;
+    }
+    let value: f64 = bytes[start..*pos].iter().collect::<String>().parse().unwrap_or(0.0);
+    sign * value
+}
+
+/// Multiplicative level (`x`/`/`), binding tighter than `parse_expr`'s additive level.
+fn parse_term(bytes: &[char], pos: &mut usize, args: &[f64]) -> f64 {
+    let mut result = parse_value(bytes, pos, args);
+
+    loop {
+        while *pos < bytes.len() && bytes[*pos] == ' ' {
+            *pos += 1;
+        }
+
+        let token = match bytes.get(*pos) {
+            Some('x') | Some('X') => Token::Mul,
+            Some('/') => Token::Div,
+            _ => break,
+        };
+        *pos += 1;
+
+        let rhs = parse_value(bytes, pos, args);
+        result = match token {
+            Token::Mul => result * rhs,
+            Token::Div => result / rhs,
+            Token::Plus | Token::Minus => unreachable!(),
+        };
+    }
+
+    result
+}
+
+/// Additive level (`+`/`-`), the lowest-precedence operators in a macro modifier expression.
+fn parse_expr(bytes: &[char], pos: &mut usize, args: &[f64]) -> f64 {
+    let mut result = parse_term(bytes, pos, args);
+
+    loop {
+        while *pos < bytes.len() && bytes[*pos] == ' ' {
+            *pos += 1;
+        }
+
+        let token = match bytes.get(*pos) {
+            Some('+') => Token::Plus,
+            Some('-') => Token::Minus,
+            _ => break,
+        };
+        *pos += 1;
+
+        let rhs = parse_term(bytes, pos, args);
+        result = match token {
+            Token::Plus => result + rhs,
+            Token::Minus => result - rhs,
+            Token::Mul | Token::Div => unreachable!(),
+        };
+    }
+
+    result
+}
+
+fn place(local_x: f64, local_y: f64, rotation: f64, target: &Point, scale: f64) -> (f64, f64) {
+    let rx = local_x * rotation.cos() - local_y * rotation.sin();
+    let ry = local_x * rotation.sin() + local_y * rotation.cos();
+    (target.x + rx * scale, target.y + ry * scale)
+}
+
+/// Order a polygon's points for exposure, reversing its winding when `exposure_on` is false
+/// so that an evenodd-filled macro flash cuts it out of the shapes accumulated so far.
+fn ordered_for_exposure(points: &[(f64, f64)], exposure_on: bool) -> Vec<(f64, f64)> {
+    let mut ordered = points.to_vec();
+    if !exposure_on {
+        ordered.reverse();
+    }
+    ordered
+}
+
+/// Append a closed polygon contour, reversing its winding when `exposure_on` is false so
+/// that an evenodd-filled macro flash cuts it out of the shapes accumulated so far.
+fn append_polygon_contour(data: path::Data, points: &[(f64, f64)], exposure_on: bool) -> path::Data {
+    let ordered = ordered_for_exposure(points, exposure_on);
+
+    let mut data = data.move_to(ordered[0]);
+    for point in &ordered[1..] {
+        data = data.line_to(*point);
+    }
+    data.close()
+}
+
+fn rotated_rect_points(cx: f64, cy: f64, width: f64, height: f64, rotation: f64) -> Vec<(f64, f64)> {
+    let hw = width / 2.0;
+    let hh = height / 2.0;
+    [(-hw, -hh), (hw, -hh), (hw, hh), (-hw, hh)]
+        .into_iter()
+        .map(|(x, y)| {
+            (
+                cx + x * rotation.cos() - y * rotation.sin(),
+                cy + x * rotation.sin() + y * rotation.cos(),
+            )
+        })
+        .collect()
+}
+
+fn track_extent(max_extent: &mut f64, target: &Point, x: f64, y: f64, radius: f64) {
+    let dist = ((x - target.x).powi(2) + (y - target.y).powi(2)).sqrt() + radius;
+    *max_extent = f64::max(*max_extent, dist);
+}
+
+/// Expand one raw primitive line (e.g. `"1,1,0.5,0,0"`) of a macro's body, appending its
+/// shape(s) onto `data` with every modifier evaluated against `args` and placed at `target`,
+/// and growing `max_extent` (the flash's reach from `target`, used for the bounding box).
+fn append_primitive(
+    mut data: path::Data,
+    max_extent: &mut f64,
+    line: &str,
+    args: &[f64],
+    target: &Point,
+    scale: f64,
+) -> path::Data {
+    let mut fields = line.split(',');
+    let Some(code) = fields.next().and_then(|c| c.trim().parse::<u32>().ok()) else {
+        return data;
+    };
+
+    let modifiers: Vec<f64> = fields.map(|m| eval_modifier(m, args)).collect();
+
+    match code {
+        // Circle: exposure, diameter, center x, center y, [rotation]
+        1 if modifiers.len() >= 4 => {
+            let (exposure, diameter, cx, cy) = (modifiers[0], modifiers[1], modifiers[2], modifiers[3]);
+            let rotation = modifiers.get(4).copied().unwrap_or(0.0).to_radians();
+            let (cx, cy) = place(cx, cy, rotation, target, scale);
+            let radius = diameter / 2.0 * scale;
+            let exposure_on = exposure != 0.0;
+
+            track_extent(max_extent, target, cx, cy, radius);
+            shapes::append_circle_contour(data, &Point::new(cx, cy), radius, !exposure_on)
+        }
+        // Vector line: exposure, width, start x, start y, end x, end y, rotation
+        20 if modifiers.len() >= 7 => {
+            let [exposure, width, x1, y1, x2, y2, rotation] = modifiers[..7] else {
+                unreachable!()
+            };
+            let rotation = rotation.to_radians();
+            let exposure_on = exposure != 0.0;
+
+            let dx = x2 - x1;
+            let dy = y2 - y1;
+            let len = (dx * dx + dy * dy).sqrt();
+            let (nx, ny) = if len > 0.0 { (-dy / len, dx / len) } else { (0.0, 1.0) };
+            let half = width / 2.0;
+
+            let points: Vec<(f64, f64)> = [
+                (x1 + nx * half, y1 + ny * half),
+                (x2 + nx * half, y2 + ny * half),
+                (x2 - nx * half, y2 - ny * half),
+                (x1 - nx * half, y1 - ny * half),
+            ]
+            .into_iter()
+            .map(|(x, y)| place(x, y, rotation, target, scale))
+            .collect();
+
+            points.iter().for_each(|(x, y)| track_extent(max_extent, target, *x, *y, 0.0));
+            append_polygon_contour(data, &points, exposure_on)
+        }
+        // Center line: exposure, width, height, center x, center y, rotation
+        21 if modifiers.len() >= 6 => {
+            let [exposure, width, height, cx, cy, rotation] = modifiers[..6] else {
+                unreachable!()
+            };
+            let rotation = rotation.to_radians();
+            let exposure_on = exposure != 0.0;
+
+            let points: Vec<(f64, f64)> = rotated_rect_points(cx, cy, width, height, rotation)
+                .into_iter()
+                .map(|(x, y)| place(x, y, 0.0, target, scale))
+                .collect();
+
+            points.iter().for_each(|(x, y)| track_extent(max_extent, target, *x, *y, 0.0));
+            append_polygon_contour(data, &points, exposure_on)
+        }
+        // Outline: exposure, vertex count n, n+1 (x, y) pairs (closed), rotation
+        4 if modifiers.len() >= 2 => {
+            let exposure_on = modifiers[0] != 0.0;
+            let vertex_count = modifiers[1] as usize;
+            let rotation = modifiers.last().copied().unwrap_or(0.0).to_radians();
+
+            let coords = &modifiers[2..modifiers.len().saturating_sub(1)];
+            let points: Vec<(f64, f64)> = coords
+                .chunks_exact(2)
+                .take(vertex_count + 1)
+                .map(|xy| place(xy[0], xy[1], rotation, target, scale))
+                .collect();
+
+            if points.len() < 3 {
+                return data;
+            }
+
+            points.iter().for_each(|(x, y)| track_extent(max_extent, target, *x, *y, 0.0));
+            append_polygon_contour(data, &points, exposure_on)
+        }
+        // Polygon: exposure, vertex count, center x, center y, diameter, rotation
+        5 if modifiers.len() >= 6 => {
+            let [exposure, vertices, cx, cy, diameter, rotation] = modifiers[..6] else {
+                unreachable!()
+            };
+            let exposure_on = exposure != 0.0;
+            let rotation = rotation.to_radians();
+            let vertices = vertices as usize;
+            let radius = diameter / 2.0;
+
+            let points: Vec<(f64, f64)> = (0..vertices)
+                .map(|k| {
+                    let angle = (2.0 * std::f64::consts::PI * k as f64) / vertices as f64;
+                    place(cx + radius * angle.cos(), cy + radius * angle.sin(), rotation, target, scale)
+                })
+                .collect();
+
+            points.iter().for_each(|(x, y)| track_extent(max_extent, target, *x, *y, 0.0));
+            append_polygon_contour(data, &points, exposure_on)
+        }
+        // Moire: center x, center y, outer diameter, ring thickness, gap, max rings,
+        // crosshair thickness, crosshair length, rotation. Always drawn (no exposure modifier).
+        6 if modifiers.len() >= 9 => {
+            let [cx, cy, outer_diameter, ring_thickness, gap, max_rings, crosshair_thickness, crosshair_length, rotation] =
+                modifiers[..9]
+            else {
+                unreachable!()
+            };
+            let rotation = rotation.to_radians();
+            let (ccx, ccy) = place(cx, cy, rotation, target, scale);
+
+            let mut outer = outer_diameter;
+            for _ in 0..max_rings as u32 {
+                if outer <= 0.0 {
+                    break;
+                }
+                let inner = (outer - 2.0 * ring_thickness).max(0.0);
+
+                track_extent(max_extent, target, ccx, ccy, outer / 2.0 * scale);
+                data = shapes::append_circle_contour(data, &Point::new(ccx, ccy), outer / 2.0 * scale, false);
+                if inner > 0.0 {
+                    data = shapes::append_circle_contour(data, &Point::new(ccx, ccy), inner / 2.0 * scale, true);
+                }
+
+                outer -= 2.0 * (ring_thickness + gap);
+            }
+
+            for axis_rotation in [0.0_f64, std::f64::consts::FRAC_PI_2] {
+                let points: Vec<(f64, f64)> =
+                    rotated_rect_points(cx, cy, crosshair_length, crosshair_thickness, rotation + axis_rotation)
+                        .into_iter()
+                        .map(|(x, y)| place(x, y, 0.0, target, scale))
+                        .collect();
+                points.iter().for_each(|(x, y)| track_extent(max_extent, target, *x, *y, 0.0));
+                data = append_polygon_contour(data, &points, true);
+            }
+
+            data
+        }
+        // Thermal: center x, center y, outer diameter, inner diameter, gap thickness, rotation.
+        // Drawn as an annulus with the crosshair gaps cut out, always exposed.
+        7 if modifiers.len() >= 6 => {
+            let [cx, cy, outer_diameter, inner_diameter, gap_thickness, rotation] = modifiers[..6] else {
+                unreachable!()
+            };
+            let rotation = rotation.to_radians();
+            let (ccx, ccy) = place(cx, cy, rotation, target, scale);
+
+            track_extent(max_extent, target, ccx, ccy, outer_diameter / 2.0 * scale);
+            data = shapes::append_circle_contour(data, &Point::new(ccx, ccy), outer_diameter / 2.0 * scale, false);
+            data = shapes::append_circle_contour(data, &Point::new(ccx, ccy), inner_diameter / 2.0 * scale, true);
+
+            for axis_rotation in [0.0_f64, std::f64::consts::FRAC_PI_2] {
+                let points: Vec<(f64, f64)> =
+                    rotated_rect_points(cx, cy, outer_diameter, gap_thickness, rotation + axis_rotation)
+                        .into_iter()
+                        .map(|(x, y)| place(x, y, 0.0, target, scale))
+                        .collect();
+                data = append_polygon_contour(data, &points, false);
+            }
+
+            data
+        }
+        other => {
+            log::warn!("Unsupported or malformed aperture macro primitive (code {other}). Skipped.");
+            data
+        }
+    }
+}
+
+/// Expand every primitive of a macro's body (one raw modifier line per primitive, in
+/// definition order) into a single path covering the whole flashed macro, plus the
+/// flash's reach from `target` (for the bounding box).
+pub(crate) fn expand(primitives: &[String], args: &[f64], target: &Point, scale: f64) -> (path::Data, f64) {
+    let mut max_extent = 0.0;
+    let data = primitives.iter().fold(path::Data::new(), |data, line| {
+        append_primitive(data, &mut max_extent, line, args, target, scale)
+    });
+
+    (data, max_extent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-9;
+
+    #[test]
+    fn eval_modifier_follows_standard_operator_precedence() {
+        // `x` (multiply) binds tighter than `+`: 2+(3*4), not (2+3)*4.
+        assert!((eval_modifier("2+3x4", &[]) - 14.0).abs() < EPSILON);
+        // Parentheses still override precedence.
+        assert!((eval_modifier("(2+3)x4", &[]) - 20.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn eval_modifier_substitutes_and_negates_arguments() {
+        assert!((eval_modifier("-$2", &[1.0, 5.0]) - (-5.0)).abs() < EPSILON);
+        assert!((eval_modifier("$1+0.5", &[1.0]) - 1.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn ordered_for_exposure_reverses_winding_when_off() {
+        let points = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)];
+        assert_eq!(ordered_for_exposure(&points, true), points);
+        assert_eq!(
+            ordered_for_exposure(&points, false),
+            vec![(1.0, 1.0), (1.0, 0.0), (0.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn expand_rounded_rect_macro_tracks_corner_circle_extent() {
+        // A rounded-rect pad: a 4x2 center-line body plus four corner circles, as real-world
+        // macro generators (e.g. KiCad) emit them.
+        let body = vec![
+            "21,1,4,2,0,0,0".to_string(),
+            "1,1,0.5,2,1,0".to_string(),
+            "1,1,0.5,-2,1,0".to_string(),
+            "1,1,0.5,2,-1,0".to_string(),
+            "1,1,0.5,-2,-1,0".to_string(),
+        ];
+        let target = Point::new(0.0, 0.0);
+
+        let (data, max_extent) = expand(&body, &[], &target, 1.0);
+
+        assert!(!data.is_empty());
+        // The corner circles (at distance sqrt(5) from center, radius 0.25) reach further
+        // than the 4x2 body's own corners (at exactly sqrt(5)).
+        assert!((max_extent - (5.0_f64.sqrt() + 0.25)).abs() < EPSILON);
+    }
+
+    #[test]
+    fn expand_thermal_pad_tracks_outer_annulus_extent() {
+        // A thermal relief: annulus from diameter 4 down to 2, with 0.3-wide crosshair gaps.
+        let body = vec!["7,0,0,4,2,0.3,0".to_string()];
+        let target = Point::new(0.0, 0.0);
+
+        let (data, max_extent) = expand(&body, &[], &target, 1.0);
+
+        assert!(!data.is_empty());
+        assert!((max_extent - 2.0).abs() < EPSILON);
+    }
+}
@@ -0,0 +1,64 @@
+//! Exact `%LP` dark/clear polarity handling via boolean polygon clipping.
+//!
+//! Unlike the default painter's-algorithm output (later shapes simply drawn on top of
+//! earlier ones), this computes the real `dark - clear` polygon set with `clipper2` so
+//! that clear-polarity flashes actually cut holes out of dark geometry underneath them,
+//! regardless of draw order. The result is emitted by the caller as a single
+//! `fill-rule="evenodd"` path, so winding direction of the resolved contours doesn't matter.
+use clipper2::{Clipper, EndType, FillRule, JoinType, PathD, PathsD, PointD};
+
+use crate::geometry::point::Point;
+
+const SEGMENTS_PER_CIRCLE: usize = 64;
+
+fn to_path(points: &[(f64, f64)]) -> PathD {
+    points.iter().map(|(x, y)| PointD::new(*x, *y)).collect()
+}
+
+fn to_paths(contours: &[Vec<(f64, f64)>]) -> PathsD {
+    contours.iter().map(|c| to_path(c)).collect()
+}
+
+fn from_paths(paths: PathsD) -> Vec<Vec<(f64, f64)>> {
+    paths
+        .iter()
+        .map(|path| path.iter().map(|p| (p.x, p.y)).collect())
+        .collect()
+}
+
+pub(crate) fn circle_contour(center: &Point, radius: f64) -> Vec<(f64, f64)> {
+    (0..SEGMENTS_PER_CIRCLE)
+        .map(|i| {
+            let angle = 2.0 * std::f64::consts::PI * i as f64 / SEGMENTS_PER_CIRCLE as f64;
+            (center.x + radius * angle.cos(), center.y + radius * angle.sin())
+        })
+        .collect()
+}
+
+pub(crate) fn rectangle_contour(center: &Point, width: f64, height: f64) -> Vec<(f64, f64)> {
+    let hw = width / 2.0;
+    let hh = height / 2.0;
+    vec![
+        (center.x - hw, center.y - hh),
+        (center.x + hw, center.y - hh),
+        (center.x + hw, center.y + hh),
+        (center.x - hw, center.y + hh),
+    ]
+}
+
+/// Offset a centerline path by half the stroking aperture's diameter, approximating the
+/// stroked outline of a trace so it can take part in the same boolean clipping as pads.
+pub(crate) fn inflate_stroke(centerline: &[(f64, f64)], diameter: f64) -> Vec<Vec<(f64, f64)>> {
+    let subject = PathsD::from(vec![to_path(centerline)]);
+    let inflated = Clipper::inflate_paths(&subject, diameter / 2.0, JoinType::Round, EndType::Round, 2.0);
+    from_paths(inflated)
+}
+
+/// Compute `dark - clear`, returning the resulting (possibly multi-contour) polygon set.
+pub(crate) fn subtract(dark: &[Vec<(f64, f64)>], clear: &[Vec<(f64, f64)>]) -> Vec<Vec<(f64, f64)>> {
+    let subject = to_paths(dark);
+    let clip = to_paths(clear);
+    let solution = Clipper::difference(&subject, &clip, FillRule::NonZero, 2.0);
+
+    from_paths(solution)
+}
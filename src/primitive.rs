@@ -0,0 +1,27 @@
+//! Shape primitives captured while walking the Gerber command stream in [`crate::Gerber2SVG::build`],
+//! independent of the backend that will render them (the SVG path emits directly; see
+//! [`crate::dxf_export`] for the DXF side).
+#[derive(Debug, Clone)]
+pub(crate) enum Primitive {
+    /// A stroked linear trace segment, `width` wide.
+    Stroke { from: (f64, f64), to: (f64, f64), width: f64 },
+
+    /// A stroked arc segment, `width` wide, swept from `start_angle` to `end_angle` (radians).
+    StrokeArc {
+        center: (f64, f64),
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+        clockwise: bool,
+        width: f64,
+    },
+
+    /// A flashed circular pad.
+    Circle { center: (f64, f64), radius: f64 },
+
+    /// A flashed polygonal pad (rectangle, obround or regular polygon), as a closed outline.
+    Polygon { contour: Vec<(f64, f64)> },
+
+    /// A filled `G36`/`G37` region, one closed contour per sub-path it contains.
+    Region { contours: Vec<Vec<(f64, f64)>> },
+}